@@ -0,0 +1,165 @@
+//! Format de trame auto-descriptif pour les flux compressés par [`crate::LZ77`].
+//!
+//! Le flux de jetons produit par [`crate::LZ77::compress`] ne porte aucune information sur la
+//! configuration utilisée, la taille d'origine, ni aucun moyen de détecter une corruption : un
+//! décodeur doit déjà connaître `max_dictionary_size`/`lookahead_buffer_size`. Ce module ajoute
+//! un en-tête (nombre magique, version, configuration, taille d'origine) suivi d'une somme de
+//! contrôle du contenu, pour obtenir un format relisible sur disque.
+
+use std::convert::TryInto;
+
+/// Nombre magique identifiant une trame lzrs (`"LZRS"` en ASCII).
+const MAGIC: [u8; 4] = *b"LZRS";
+/// Version actuelle du format de trame, écrite par [`encode_frame`] : la position d'un jeton
+/// commence par une étiquette d'un octet qui résout directement les correspondances répétées
+/// vers un emplacement de la file de distances récentes, au lieu de stocker une position sur 2
+/// octets même pour ce cas (voir [`crate::LZ77::encode`]). Une correspondance répétée ne coûte
+/// donc plus qu'un octet de position au lieu de 2.
+const VERSION: u8 = 5;
+/// Version intermédiaire du format de trame, dont la position d'un jeton occupait toujours 2
+/// octets, y compris pour référencer la file de distances récentes via une valeur sentinelle en
+/// haut de plage. Conservée pour que les trames produites avant l'introduction de l'étiquette de
+/// position compacte restent décodables (voir [`crate::LZ77::decode_v4`]).
+const VERSION_PRESENCE_FLAG: u8 = 4;
+/// Version intermédiaire du format de trame, dont les jetons ont une longueur variable et une
+/// résolution des correspondances répétées via une file de distances récentes (voir
+/// `crate::recent_offsets::RecentOffsets`), mais dont le dernier jeton porte toujours un
+/// caractère suivant (quitte à valoir `0` sans signification). Conservée pour que les trames
+/// produites avant l'introduction du drapeau de présence restent décodables (voir
+/// [`crate::LZ77::decode_v3`]).
+const VERSION_RECENT_OFFSETS: u8 = 3;
+/// Version intermédiaire du format de trame, dont les jetons ont une longueur variable mais
+/// sans file de distances récentes. Conservée pour que les trames produites avant
+/// l'introduction de cette file restent décodables.
+const VERSION_EXTENDED_LENGTH: u8 = 2;
+/// Ancienne version du format de trame, dont les jetons ont une largeur fixe de 3 octets
+/// (distance sur 12 bits, longueur sur 4 bits). Conservée pour que les trames produites avant
+/// l'introduction de l'encodage étendu restent décodables.
+const LEGACY_VERSION: u8 = 1;
+/// Taille de l'en-tête en octets : magic (4) + version (1) + dictionnaire (4) + tampon
+/// d'anticipation (4) + longueur d'origine (8).
+const HEADER_SIZE: usize = 4 + 1 + 4 + 4 + 8;
+/// Taille de la somme de contrôle finale (CRC-32), en octets.
+const CHECKSUM_SIZE: usize = 4;
+
+/// Erreur renvoyée lorsqu'une trame ne peut pas être décompressée.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FrameError {
+    /// La trame est trop courte pour contenir un en-tête et une somme de contrôle.
+    Truncated,
+    /// Les quatre premiers octets ne correspondent pas au nombre magique attendu.
+    InvalidMagic,
+    /// La version de la trame n'est pas prise en charge par cette implémentation.
+    UnsupportedVersion(u8),
+    /// La somme de contrôle calculée sur les données décompressées ne correspond pas à celle
+    /// stockée dans la trame : les données sont corrompues.
+    ChecksumMismatch,
+}
+
+impl std::fmt::Display for FrameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FrameError::Truncated => write!(f, "trame tronquée"),
+            FrameError::InvalidMagic => write!(f, "nombre magique invalide"),
+            FrameError::UnsupportedVersion(version) => {
+                write!(f, "version de trame non prise en charge : {version}")
+            }
+            FrameError::ChecksumMismatch => {
+                write!(f, "somme de contrôle invalide : les données sont corrompues")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FrameError {}
+
+/// Calcule le CRC-32 (IEEE 802.3) de `data`.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0xFFFFFFFF;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask: u32 = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB88320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Encode `token_stream` (déjà produit par [`crate::LZ77::compress`]) dans une trame
+/// auto-descriptive incluant la configuration utilisée, la taille d'origine et une somme de
+/// contrôle des données brutes.
+pub(crate) fn encode_frame(
+    raw_data: &[u8],
+    token_stream: &[u8],
+    max_dictionary_size: usize,
+    lookahead_buffer_size: usize,
+) -> Vec<u8> {
+    let mut framed: Vec<u8> = Vec::with_capacity(HEADER_SIZE + token_stream.len() + CHECKSUM_SIZE);
+    framed.extend_from_slice(&MAGIC);
+    framed.push(VERSION);
+    framed.extend_from_slice(&(max_dictionary_size as u32).to_le_bytes());
+    framed.extend_from_slice(&(lookahead_buffer_size as u32).to_le_bytes());
+    framed.extend_from_slice(&(raw_data.len() as u64).to_le_bytes());
+    framed.extend_from_slice(token_stream);
+    framed.extend_from_slice(&crc32(raw_data).to_le_bytes());
+    framed
+}
+
+/// Résultat du décodage d'une trame : le flux de jetons encore compressé ainsi que la
+/// configuration et la taille d'origine enregistrées dans l'en-tête.
+pub(crate) struct DecodedFrame<'a> {
+    pub(crate) token_stream: &'a [u8],
+    /// Version du format de jeton utilisée par `token_stream` : voir [`VERSION`],
+    /// [`VERSION_PRESENCE_FLAG`], [`VERSION_RECENT_OFFSETS`], [`VERSION_EXTENDED_LENGTH`] et
+    /// [`LEGACY_VERSION`].
+    pub(crate) version: u8,
+    /// Conservé pour que les futurs formats de jetons puissent s'auto-configurer ; les formats
+    /// de jeton actuels n'en ont pas besoin pour décoder.
+    #[allow(dead_code)]
+    pub(crate) max_dictionary_size: usize,
+    #[allow(dead_code)]
+    pub(crate) lookahead_buffer_size: usize,
+    pub(crate) original_length: usize,
+    pub(crate) checksum: u32,
+}
+
+/// Valide l'en-tête de `framed` et en extrait le flux de jetons ainsi que la configuration et
+/// la somme de contrôle enregistrées, sans vérifier la somme de contrôle (qui ne peut être
+/// vérifiée qu'une fois les données décompressées).
+pub(crate) fn decode_frame(framed: &[u8]) -> Result<DecodedFrame<'_>, FrameError> {
+    if framed.len() < HEADER_SIZE + CHECKSUM_SIZE {
+        return Err(FrameError::Truncated);
+    }
+    if framed[0..4] != MAGIC {
+        return Err(FrameError::InvalidMagic);
+    }
+    let version: u8 = framed[4];
+    if version != VERSION
+        && version != VERSION_PRESENCE_FLAG
+        && version != VERSION_RECENT_OFFSETS
+        && version != VERSION_EXTENDED_LENGTH
+        && version != LEGACY_VERSION
+    {
+        return Err(FrameError::UnsupportedVersion(version));
+    }
+
+    let max_dictionary_size: usize =
+        u32::from_le_bytes(framed[5..9].try_into().unwrap()) as usize;
+    let lookahead_buffer_size: usize =
+        u32::from_le_bytes(framed[9..13].try_into().unwrap()) as usize;
+    let original_length: usize = u64::from_le_bytes(framed[13..21].try_into().unwrap()) as usize;
+
+    let token_stream: &[u8] = &framed[HEADER_SIZE..framed.len() - CHECKSUM_SIZE];
+    let checksum: u32 =
+        u32::from_le_bytes(framed[framed.len() - CHECKSUM_SIZE..].try_into().unwrap());
+
+    Ok(DecodedFrame {
+        token_stream,
+        version,
+        max_dictionary_size,
+        lookahead_buffer_size,
+        original_length,
+        checksum,
+    })
+}
@@ -0,0 +1,152 @@
+//! Structure interne utilisée par [`crate::LZ77`] pour accélérer la recherche de
+//! correspondances pendant la compression.
+//!
+//! L'ancienne recherche reconstruisait toutes les fenêtres du tampon de recherche à chaque
+//! position, ce qui est quadratique sur de grandes entrées. `HashChain` indexe plutôt chaque
+//! position par le hash de ses `MIN_MATCH` premiers octets et relie les positions partageant le
+//! même hash par une chaîne, ce qui permet de ne comparer que les candidats plausibles.
+
+/// Nombre d'octets utilisés pour calculer le hash d'une position.
+const MIN_MATCH: usize = 3;
+/// Nombre de bits utilisés pour indexer la table de hachage.
+const HASH_BITS: u32 = 15;
+/// Nombre d'entrées de la table de hachage (2^HASH_BITS).
+const HASH_SIZE: usize = 1 << HASH_BITS;
+/// Profondeur maximale de parcours des chaînes par défaut.
+pub(crate) const DEFAULT_MAX_CHAIN: usize = 128;
+
+/// Table de hachage et chaîne de positions précédentes, utilisées pour retrouver rapidement
+/// les occurrences passées des `MIN_MATCH` prochains octets.
+///
+/// `head[hash]` contient la position la plus récente ayant ce hash, et `prev[position]`
+/// contient la position précédente partageant le même hash (ou `-1` s'il n'y en a pas),
+/// formant ainsi une chaîne que l'on remonte dans le temps.
+#[derive(Debug)]
+pub(crate) struct HashChain {
+    head: Vec<isize>,
+    prev: Vec<isize>,
+    max_chain: usize,
+}
+
+impl HashChain {
+    /// Crée une nouvelle chaîne de hachage capable d'indexer des positions sur `capacity`
+    /// octets de données, en ne remontant jamais plus de `max_chain` maillons par recherche.
+    pub(crate) fn new(capacity: usize, max_chain: usize) -> Self {
+        HashChain {
+            head: vec![-1; HASH_SIZE],
+            prev: vec![-1; capacity],
+            max_chain,
+        }
+    }
+
+    /// Calcule le hash des `MIN_MATCH` premiers octets de `bytes`.
+    fn hash(bytes: &[u8]) -> usize {
+        let word: u32 =
+            bytes[0] as u32 | (bytes[1] as u32) << 8 | (bytes[2] as u32) << 16;
+        ((word.wrapping_mul(2654435761)) >> (32 - HASH_BITS)) as usize
+    }
+
+    /// Insère la position `pos` de `data` dans la table de hachage, pour qu'elle puisse être
+    /// retrouvée par une future recherche. Ne fait rien s'il ne reste pas `MIN_MATCH` octets
+    /// à partir de `pos`. Si `pos` dépasse la capacité allouée à la construction, la chaîne des
+    /// positions précédentes est agrandie au besoin (voir [`Encoder`](crate::Encoder), qui ne
+    /// connaît pas la longueur totale du flux à l'avance).
+    pub(crate) fn insert(&mut self, pos: usize, data: &[u8]) {
+        if pos + MIN_MATCH > data.len() {
+            return;
+        }
+        if pos >= self.prev.len() {
+            self.prev.resize(pos + 1, -1);
+        }
+        let h: usize = Self::hash(&data[pos..pos + MIN_MATCH]);
+        self.prev[pos] = self.head[h];
+        self.head[h] = pos as isize;
+    }
+
+    /// Décale de `shift` positions toutes les références conservées (table de hachage et
+    /// chaînes), pour accompagner le retrait des `shift` premiers octets de `data` (voir
+    /// [`Encoder::drain_tokens`](crate::Encoder), qui borne la mémoire du flux en glissant la
+    /// fenêtre de recherche). Une référence qui tombait dans la portion retirée est invalidée.
+    pub(crate) fn rebase(&mut self, shift: usize) {
+        for head in self.head.iter_mut() {
+            *head = rebase_reference(*head, shift);
+        }
+        if shift >= self.prev.len() {
+            self.prev.clear();
+        } else {
+            self.prev.drain(..shift);
+            for prev in self.prev.iter_mut() {
+                *prev = rebase_reference(*prev, shift);
+            }
+        }
+    }
+
+    /// Recherche la plus longue correspondance pour la position `pos` de `data`.
+    ///
+    /// Seules les positions situées à une distance inférieure ou égale à `max_distance` de
+    /// `pos` sont considérées, et les correspondances sont bornées à `lookahead_len` octets.
+    /// Le parcours de la chaîne s'arrête après `max_chain` maillons pour borner le coût dans
+    /// le pire des cas.
+    ///
+    /// Retourne un couple `(distance, longueur)`, ou `(0, 0)` si aucune correspondance n'a été
+    /// trouvée.
+    pub(crate) fn find_match(
+        &self,
+        pos: usize,
+        data: &[u8],
+        max_distance: usize,
+        lookahead_len: usize,
+    ) -> (usize, usize) {
+        if lookahead_len < MIN_MATCH || pos + MIN_MATCH > data.len() {
+            return (0, 0);
+        }
+
+        let max_len: usize = lookahead_len.min(data.len() - pos);
+        let h: usize = Self::hash(&data[pos..pos + MIN_MATCH]);
+        let mut candidate: isize = self.head[h];
+        let mut best_length: usize = 0;
+        let mut best_distance: usize = 0;
+        let mut steps: usize = 0;
+
+        while candidate >= 0 && steps < self.max_chain {
+            let candidate_pos: usize = candidate as usize;
+            let distance: usize = pos - candidate_pos;
+            if distance > max_distance {
+                break;
+            }
+
+            let mut length: usize = 0;
+            while length < max_len && data[candidate_pos + length] == data[pos + length] {
+                length += 1;
+            }
+
+            if length > best_length {
+                best_length = length;
+                best_distance = distance;
+            }
+
+            candidate = self.prev[candidate_pos];
+            steps += 1;
+        }
+
+        if best_length == 0 {
+            (0, 0)
+        } else {
+            (best_distance, best_length)
+        }
+    }
+}
+
+/// Décale une référence de position (`head`/`prev`) de `shift`, ou l'invalide (`-1`) si elle
+/// tombait dans la portion retirée. Utilisée par [`HashChain::rebase`].
+fn rebase_reference(reference: isize, shift: usize) -> isize {
+    if reference < 0 {
+        return reference;
+    }
+    let shifted: isize = reference - shift as isize;
+    if shifted < 0 {
+        -1
+    } else {
+        shifted
+    }
+}
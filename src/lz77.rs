@@ -1,4 +1,30 @@
-use crate::{find_longest_match, LZ};
+use crate::frame::{crc32, decode_frame, encode_frame, FrameError};
+use crate::hash_chain::{HashChain, DEFAULT_MAX_CHAIN};
+use crate::recent_offsets::{self, RecentOffsets};
+use crate::{Decoder, Encoder, LZ};
+
+/// Longueur de correspondance maximale recherchée par [`LZ77::compress`], dans la gamme des
+/// encodeurs de la famille DEFLATE (voir libflate_lz77's `MAX_LENGTH`).
+pub const MAX_LENGTH: usize = 258;
+/// Distance de correspondance maximale recherchée par [`LZ77::compress`], dans la gamme des
+/// encodeurs de la famille DEFLATE ; elle tient sans perte dans les 2 octets de distance de
+/// [`LZ77::encode`].
+pub const MAX_DISTANCE: usize = 32768;
+/// Valeur d'étiquette de position signifiant "pas de correspondance" (littéral seul) : voir
+/// [`LZ77::encode`].
+pub(crate) const POSITION_TAG_NO_MATCH: u8 = 0;
+/// Valeur d'étiquette de position signifiant "distance complète" : les 2 octets qui suivent
+/// encodent la distance réelle (voir [`MAX_DISTANCE`]). Les étiquettes `1..=CAPACITY` sont
+/// réservées aux références à la file des distances récentes (voir [`RecentOffsets`]), donc
+/// cette valeur d'échappement se place juste après.
+pub(crate) const POSITION_TAG_FULL_DISTANCE: u8 = recent_offsets::CAPACITY as u8 + 1;
+/// Plus haute valeur de position (sur 2 octets) : dans les formats de trame antérieurs à
+/// l'introduction de l'étiquette de position (voir [`POSITION_TAG_FULL_DISTANCE`]), les
+/// [`recent_offsets::CAPACITY`] valeurs qui se terminent par celle-ci référençaient
+/// l'emplacement `LEGACY_REPEAT_OFFSET_SENTINEL - valeur` de la file des distances récentes. Conservée
+/// uniquement pour que [`LZ77::decode_v3`] et [`LZ77::decode_v4`] puissent encore décoder les
+/// trames produites avec ce schéma.
+const LEGACY_REPEAT_OFFSET_SENTINEL: u16 = u16::MAX;
 
 #[derive(Debug)]
 pub struct LZ77 {
@@ -6,11 +32,20 @@ pub struct LZ77 {
     max_dictionary_size: usize,
     /// Taille du tampon de recherche.
     lookahead_buffer_size: usize,
+    /// Profondeur maximale de parcours de la chaîne de hachage lors de la recherche de
+    /// correspondances (voir [`crate::hash_chain::HashChain`]).
+    max_chain: usize,
+    /// Dictionnaire prédéfini utilisé pour amorcer le tampon de recherche avant le premier
+    /// octet des données (voir [`LZ77::with_dictionary`]).
+    preset_dictionary: Vec<u8>,
+    /// Active l'appariement paresseux (voir [`LZ77::set_lazy_matching`]).
+    lazy_matching: bool,
 }
 
 impl LZ77 {
-    /// Crée une nouvelle instance de LZ77, par defaut le dictionnaire de recherche fait 12 bits (4095 usize)
-    /// et le tampon de recherche est de 4 bits (15 usize)
+    /// Crée une nouvelle instance de LZ77, par defaut le dictionnaire de recherche fait 12 bits
+    /// (4095 usize) et le tampon de recherche vaut [`MAX_LENGTH`], pour que les correspondances
+    /// puissent atteindre la longueur maximale recherchée par [`LZ77::compress`].
     ///
     /// # Exemple
     ///
@@ -23,10 +58,105 @@ impl LZ77 {
     pub fn new() -> Self {
         LZ77 {
             max_dictionary_size: 4095, // 12 bits
-            lookahead_buffer_size: 15, // 4 bits
+            lookahead_buffer_size: MAX_LENGTH,
+            max_chain: DEFAULT_MAX_CHAIN,
+            preset_dictionary: Vec::new(),
+            lazy_matching: false,
         }
     }
 
+    /// Crée une nouvelle instance de LZ77 amorcée avec `dictionary` : le tampon de recherche
+    /// est initialisé avec ces octets avant même le premier octet des données à compresser,
+    /// ce qui permet aux correspondances de s'y référer (distances "négatives" par rapport au
+    /// début des données). `compress` et `decompress` doivent utiliser le même dictionnaire.
+    ///
+    /// Particulièrement utile pour compresser de nombreux messages courts et indépendants
+    /// (lignes de log, enregistrements JSON, ...) qui partagent un préfixe ou des
+    /// sous-séquences communes : sans dictionnaire, chacun commencerait avec un tampon de
+    /// recherche vide et compresserait mal.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use lzrs::{LZ77, LZ};
+    ///
+    /// // Crée une instance de LZ77 amorcée avec un dictionnaire.
+    /// let lz77 = LZ77::with_dictionary(b"hello world");
+    ///
+    /// let compressed_data = lz77.compress(b"hello there");
+    /// let decompressed_data = lz77.decompress(&compressed_data);
+    /// assert_eq!(decompressed_data, b"hello there");
+    /// ```
+    pub fn with_dictionary(dictionary: &[u8]) -> Self {
+        let mut lz77: LZ77 = LZ77::new();
+        lz77.set_dictionary(dictionary);
+        lz77
+    }
+
+    /// Obtenir le dictionnaire prédéfini utilisé pour amorcer le tampon de recherche.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use lzrs::LZ77;
+    ///
+    /// let lz77 = LZ77::with_dictionary(b"hello world");
+    /// assert_eq!(lz77.get_dictionary(), b"hello world");
+    /// ```
+    pub fn get_dictionary(&self) -> &[u8] {
+        &self.preset_dictionary
+    }
+
+    /// Définir un nouveau dictionnaire prédéfini utilisé pour amorcer le tampon de recherche.
+    /// `compress` et `decompress` doivent utiliser le même dictionnaire pour qu'une
+    /// correspondance s'y référant soit résolue correctement.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use lzrs::LZ77;
+    ///
+    /// let mut lz77 = LZ77::new();
+    /// lz77.set_dictionary(b"hello world");
+    /// ```
+    pub fn set_dictionary(&mut self, dictionary: &[u8]) {
+        self.preset_dictionary = dictionary.to_vec();
+    }
+
+    /// Indique si l'appariement paresseux (lazy matching) est activé.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use lzrs::LZ77;
+    ///
+    /// let lz77 = LZ77::new();
+    /// assert_eq!(lz77.get_lazy_matching(), false);
+    /// ```
+    pub fn get_lazy_matching(&self) -> bool {
+        self.lazy_matching
+    }
+
+    /// Active ou désactive l'appariement paresseux (lazy matching).
+    ///
+    /// Lorsqu'il est activé, `compress` ne valide la meilleure correspondance trouvée en
+    /// position `i` que si la position `i + 1` n'offre pas une correspondance strictement
+    /// plus longue ; sinon un littéral est émis pour l'octet `i` et la décision est reportée
+    /// d'un octet. Cela augmente le taux de compression au prix d'une recherche
+    /// supplémentaire par position.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use lzrs::LZ77;
+    ///
+    /// let mut lz77 = LZ77::new();
+    /// lz77.set_lazy_matching(true);
+    /// ```
+    pub fn set_lazy_matching(&mut self, lazy_matching: bool) {
+        self.lazy_matching = lazy_matching;
+    }
+
     /// Obtenir la taille maximale du dictionnaire de recherche.
     ///
     /// # Exemple
@@ -95,78 +225,400 @@ impl LZ77 {
         self.lookahead_buffer_size = new_size;
     }
 
-    /// Encode les informations de position, longueur et caractère suivant dans le vecteur compressé.
+    /// Obtenir la profondeur maximale de parcours de la chaîne de hachage lors de la
+    /// recherche de correspondances.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use lzrs::LZ77;
+    ///
+    /// // Crée une nouvelle instance de LZ77.
+    /// let lz77 = LZ77::new();
+    ///
+    /// // Obtient la profondeur maximale de la chaîne.
+    /// let max_chain = lz77.get_max_chain();
+    /// ```
+    pub fn get_max_chain(&self) -> usize {
+        self.max_chain
+    }
+
+    /// Définir une nouvelle profondeur maximale de parcours de la chaîne de hachage lors de
+    /// la recherche de correspondances. Une valeur plus grande trouve de meilleures
+    /// correspondances au prix d'une compression plus lente ; une valeur plus petite accélère
+    /// la compression au prix du taux de compression.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use lzrs::LZ77;
+    ///
+    /// // Crée une nouvelle instance de LZ77.
+    /// let mut lz77 = LZ77::new();
+    ///
+    /// // Définit une nouvelle profondeur maximale pour la chaîne de hachage.
+    /// lz77.set_max_chain(32);
+    /// ```
+    pub fn set_max_chain(&mut self, new_max_chain: usize) {
+        self.max_chain = new_max_chain;
+    }
+
+    /// Encode les informations de position, longueur et caractère suivant dans le vecteur
+    /// compressé.
+    ///
+    /// La position commence par une étiquette d'un octet : `0` signifie "pas de
+    /// correspondance", `1..=`[`recent_offsets::CAPACITY`]` signifie "correspondance répétée"
+    /// (la distance est déjà dans `recent_offsets`, l'étiquette porte directement son
+    /// emplacement dans la file), et [`POSITION_TAG_FULL_DISTANCE`] signifie "distance complète
+    /// sur les 2 octets suivants" (jusqu'à [`MAX_DISTANCE`]). Une correspondance répétée ne
+    /// coûte donc qu'un seul octet de position au lieu de 2, ce qui paie sur les données à
+    /// structure périodique (voir [`RecentOffsets`]). Dans tous les cas, la longueur est
+    /// encodée par un ou plusieurs octets : chaque octet valant `0xFF` signifie "ajouter 255 et
+    /// continuer", le premier octet strictement inférieur à `0xFF` terminant la longueur. Cela
+    /// permet d'encoder des correspondances bien plus longues que les 15 octets du format
+    /// d'origine (jusqu'à [`MAX_LENGTH`]), sans jamais plafonner la longueur représentable.
+    ///
+    /// `next_character` est `None` lorsqu'il n'y a plus aucun octet après la correspondance
+    /// (fin des données compressées) : un octet drapeau (`0` ou `1`) précède alors le caractère
+    /// pour le signaler explicitement, plutôt que de coder son absence par un octet `0` qui
+    /// serait indiscernable d'un véritable octet nul de donnée.
     ///
     /// # Arguments
     ///
     /// * `compressed_data` - Vecteur où les données compressées sont stockées.
+    /// * `recent_offsets` - File des distances récemment utilisées, mise à jour par l'appel.
     /// * `position` - Position de début de la correspondance dans le dictionnaire.
     /// * `length` - Longueur de la correspondance.
-    /// * `next_character` - Caractère suivant dans la séquence.
+    /// * `next_character` - Caractère suivant dans la séquence, ou `None` en fin de données.
     ///
     /// # Exemple
     ///
     /// ```rust
-    /// use lzrs::LZ77;
+    /// use lzrs::{LZ77, RecentOffsets};
     ///
     /// // Vecteur pour stocker les données compressées.
     /// let mut compressed_data = Vec::new();
+    /// let mut recent_offsets = RecentOffsets::new();
     ///
-    /// // Ajoute une entrée compressée au vecteur.
-    /// LZ77::encode(&mut compressed_data, 10, 3, b'a');
+    /// // Ajoute une entrée compressée au vecteur : distance complète, encore inconnue de
+    /// // `recent_offsets`, donc étiquette d'échappement suivie de la distance sur 2 octets.
+    /// LZ77::encode(&mut compressed_data, &mut recent_offsets, 10, 3, Some(b'a'));
     /// // Vérifie que les données compressées correspondent aux attentes.
-    /// assert_eq!(compressed_data, vec![10, 48, b'a']);
-    /// ```
+    /// assert_eq!(compressed_data, vec![4, 10, 0, 3, 1, b'a']);
     /// ```
     pub fn encode(
         compressed_data: &mut Vec<u8>,
+        recent_offsets: &mut RecentOffsets,
         position: usize,
         length: usize,
-        next_charracter: u8,
+        next_character: Option<u8>,
     ) {
-        compressed_data.push((position & 0x000000FF) as u8);
-        compressed_data
-            .push(((position & 0x00000F00) >> 8) as u8 | ((length & 0x0000000F) << 4) as u8);
-        compressed_data.push(next_charracter);
+        if position == 0 && length == 0 {
+            compressed_data.push(POSITION_TAG_NO_MATCH);
+        } else if let Some(slot) = recent_offsets.record(position) {
+            compressed_data.push(slot + 1);
+        } else {
+            compressed_data.push(POSITION_TAG_FULL_DISTANCE);
+            compressed_data.extend_from_slice(&(position as u16).to_le_bytes());
+        }
+        let mut remaining: usize = length;
+        while remaining >= 0xFF {
+            compressed_data.push(0xFF);
+            remaining -= 0xFF;
+        }
+        compressed_data.push(remaining as u8);
+        match next_character {
+            Some(byte) => {
+                compressed_data.push(1);
+                compressed_data.push(byte);
+            }
+            None => compressed_data.push(0),
+        }
     }
-    /// Décode les informations de position, longueur et caractère suivant à partir du chunk donné
-    /// et met à jour le buffer avec les données décompressées.
+
+    /// Décode un jeton de longueur variable (position, longueur, caractère suivant) à partir
+    /// du début de `chunk` et met à jour le buffer avec les données décompressées.
+    ///
+    /// L'étiquette de position lue en tête de `chunk` indique comment la distance doit être
+    /// retrouvée (voir [`LZ77::encode`]) : `1..=`[`recent_offsets::CAPACITY`]` la résout via
+    /// `recent_offsets`, qui doit être la même file (dans le même état) que celle utilisée par
+    /// [`LZ77::encode`] pour produire ce flux.
+    ///
+    /// Le caractère suivant est précédé d'un octet drapeau (`0` ou `1`) qui indique s'il est
+    /// présent : c'est ce qui permet à [`LZ77::decompress`] de s'arrêter sans avoir à deviner
+    /// si le dernier octet produit fait partie des données ou n'est qu'un artefact d'encodage
+    /// (voir [`LZ77::encode`]).
+    ///
+    /// `chunk` peut contenir plus d'octets qu'un seul jeton : seuls ceux nécessaires à ce
+    /// jeton sont lus. Retourne le nombre d'octets consommés, pour que l'appelant puisse
+    /// avancer jusqu'au jeton suivant (voir [`LZ77::decompress`]).
     ///
     /// # Arguments
     ///
     /// * `buffer` - Vecteur contenant les données décompressées.
-    /// * `chunk` - Chunk de données compressées à décoder.
+    /// * `recent_offsets` - File des distances récemment utilisées, mise à jour par l'appel.
+    /// * `chunk` - Données compressées à décoder, à partir d'un début de jeton.
     ///
     /// # Exemple
     ///
     /// ```rust
-    /// use lzrs::LZ77;
+    /// use lzrs::{LZ77, RecentOffsets};
     ///
     /// // Vecteur pour stocker les données décompressées.
     /// let mut buffer = Vec::new();
+    /// let mut recent_offsets = RecentOffsets::new();
     ///
-    /// let chunk = [10, 48, b'a'];
+    /// let chunk = [4, 10, 0, 3, 1, b'a'];
     ///
     /// // Décode le chunk et met à jour le buffer.
-    /// LZ77::decode(&mut buffer, &chunk);
+    /// let consumed = LZ77::decode(&mut buffer, &mut recent_offsets, &chunk);
     ///
     /// assert_eq!(buffer, vec![b'a'; 1]);
-    ///
+    /// assert_eq!(consumed, 6);
     /// ```
-    pub fn decode(buffer: &mut Vec<u8>, chunk: &[u8]) {
-        let position: usize = chunk[0] as usize | ((chunk[1] as usize & 0x0F) << 8);
-        let length: usize = (chunk[1] >> 4) as usize;
+    pub fn decode(buffer: &mut Vec<u8>, recent_offsets: &mut RecentOffsets, chunk: &[u8]) -> usize {
+        let tag: u8 = match chunk.first() {
+            Some(&tag) => tag,
+            None => return chunk.len(),
+        };
+        let mut index: usize = 1;
+
+        // `resolve` ne panique jamais : un `slot` hors limites (flux corrompu) se résout en `0`.
+        let position: usize = if tag == POSITION_TAG_NO_MATCH {
+            0
+        } else if tag == POSITION_TAG_FULL_DISTANCE {
+            let bytes: [u8; 2] = match chunk.get(index..index + 2) {
+                Some(slice) => [slice[0], slice[1]],
+                None => return chunk.len(),
+            };
+            index += 2;
+            let distance: usize = u16::from_le_bytes(bytes) as usize;
+            recent_offsets.record(distance);
+            distance
+        } else {
+            recent_offsets.resolve(tag - 1)
+        };
+
+        let mut length: usize = 0;
+        loop {
+            let byte: u8 = match chunk.get(index) {
+                Some(&byte) => byte,
+                None => return chunk.len(),
+            };
+            index += 1;
+            length += byte as usize;
+            if byte != 0xFF {
+                break;
+            }
+        }
+
         // Vérifie si la position et la longueur indiquent une référence au dictionnaire.
+        if !(position == 0 && length == 0) {
+            // Calcule l'indice de début dans le buffer à partir duquel recopier.
+            let start: usize = buffer.len().saturating_sub(position);
+            // Recopie octet par octet plutôt que par tranche : une correspondance peut se
+            // chevaucher avec les octets qu'elle vient elle-même de produire (distance
+            // inférieure à la longueur), comme dans une expansion par longueur de course.
+            if start < buffer.len() {
+                for offset in 0..length {
+                    let byte: u8 = buffer[start + offset];
+                    buffer.push(byte);
+                }
+            }
+        }
+        let has_next_character: u8 = match chunk.get(index) {
+            Some(&byte) => byte,
+            None => return chunk.len(),
+        };
+        index += 1;
+        if has_next_character != 0 {
+            match chunk.get(index) {
+                Some(&byte) => {
+                    buffer.push(byte);
+                    index += 1;
+                }
+                None => return chunk.len(),
+            }
+        }
+        index
+    }
+
+    /// Décode un jeton selon le format de trame version 4, dont la position était toujours
+    /// stockée sur 2 octets (même pour une correspondance répétée, voir
+    /// [`LEGACY_REPEAT_OFFSET_SENTINEL`]), contrairement au format actuel qui ne dépense qu'un
+    /// octet d'étiquette pour ce cas (voir [`LZ77::decode`]). Conservé uniquement pour que
+    /// [`LZ77::decompress_frame`] puisse encore décoder les trames version 4, produites avant
+    /// l'introduction de l'étiquette de position compacte.
+    fn decode_v4(buffer: &mut Vec<u8>, recent_offsets: &mut RecentOffsets, chunk: &[u8]) -> usize {
+        if chunk.len() < 2 {
+            return chunk.len();
+        }
+        let encoded_position: usize = u16::from_le_bytes([chunk[0], chunk[1]]) as usize;
+
+        let mut index: usize = 2;
+        let mut length: usize = 0;
+        loop {
+            let byte: u8 = match chunk.get(index) {
+                Some(&byte) => byte,
+                None => return chunk.len(),
+            };
+            index += 1;
+            length += byte as usize;
+            if byte != 0xFF {
+                break;
+            }
+        }
+
+        let repeat_offset_floor: usize =
+            LEGACY_REPEAT_OFFSET_SENTINEL as usize + 1 - recent_offsets::CAPACITY;
+        let position: usize = if encoded_position == 0 {
+            0
+        } else if encoded_position >= repeat_offset_floor {
+            let slot: u8 = (LEGACY_REPEAT_OFFSET_SENTINEL as usize - encoded_position) as u8;
+            recent_offsets.resolve(slot)
+        } else {
+            recent_offsets.record(encoded_position);
+            encoded_position
+        };
 
         if !(position == 0 && length == 0) {
-            // Calcule les indices de début et de fin dans le buffer pour extraire la fenêtre correspondante.
+            let start: usize = buffer.len().saturating_sub(position);
+            if start < buffer.len() {
+                for offset in 0..length {
+                    let byte: u8 = buffer[start + offset];
+                    buffer.push(byte);
+                }
+            }
+        }
+        let has_next_character: u8 = match chunk.get(index) {
+            Some(&byte) => byte,
+            None => return chunk.len(),
+        };
+        index += 1;
+        if has_next_character != 0 {
+            match chunk.get(index) {
+                Some(&byte) => {
+                    buffer.push(byte);
+                    index += 1;
+                }
+                None => return chunk.len(),
+            }
+        }
+        index
+    }
+
+    /// Décode un jeton selon le format de trame version 3, dont le dernier jeton porte un
+    /// caractère suivant systématiquement présent (quitte à valoir `0` sans signification),
+    /// contrairement au format actuel (voir [`LZ77::decode`]). Conservé uniquement pour que
+    /// [`LZ77::decompress_frame`] puisse encore décoder les trames version 3, produites avant
+    /// l'introduction du drapeau de présence.
+    fn decode_v3(buffer: &mut Vec<u8>, recent_offsets: &mut RecentOffsets, chunk: &[u8]) -> usize {
+        if chunk.len() < 2 {
+            return chunk.len();
+        }
+        let encoded_position: usize = u16::from_le_bytes([chunk[0], chunk[1]]) as usize;
 
+        let mut index: usize = 2;
+        let mut length: usize = 0;
+        loop {
+            let byte: u8 = match chunk.get(index) {
+                Some(&byte) => byte,
+                None => return chunk.len(),
+            };
+            index += 1;
+            length += byte as usize;
+            if byte != 0xFF {
+                break;
+            }
+        }
+
+        let repeat_offset_floor: usize =
+            LEGACY_REPEAT_OFFSET_SENTINEL as usize + 1 - recent_offsets::CAPACITY;
+        let position: usize = if encoded_position == 0 {
+            0
+        } else if encoded_position >= repeat_offset_floor {
+            let slot: u8 = (LEGACY_REPEAT_OFFSET_SENTINEL as usize - encoded_position) as u8;
+            recent_offsets.resolve(slot)
+        } else {
+            recent_offsets.record(encoded_position);
+            encoded_position
+        };
+
+        if !(position == 0 && length == 0) {
             let start: usize = buffer.len().saturating_sub(position);
-            let end: usize = start + length;
-            // Extrait la fenêtre du buffer et l'étend à la fin du buffer.
             if start < buffer.len() {
-                let window: Vec<u8> = buffer[start..end].to_vec();
-                buffer.extend_from_slice(&window);
+                for offset in 0..length {
+                    let byte: u8 = buffer[start + offset];
+                    buffer.push(byte);
+                }
+            }
+        }
+        match chunk.get(index) {
+            Some(&byte) => {
+                buffer.push(byte);
+                index + 1
+            }
+            None => chunk.len(),
+        }
+    }
+
+    /// Décode un jeton selon le format de longueur variable introduit par l'encodage étendu,
+    /// sans résolution de distances récentes. Conservé uniquement pour que
+    /// [`LZ77::decompress_frame`] puisse encore décoder les trames version 2, produites avant
+    /// l'introduction de la file de distances récentes.
+    fn decode_without_recent_offsets(buffer: &mut Vec<u8>, chunk: &[u8]) -> usize {
+        if chunk.len() < 2 {
+            return chunk.len();
+        }
+        let position: usize = u16::from_le_bytes([chunk[0], chunk[1]]) as usize;
+
+        let mut index: usize = 2;
+        let mut length: usize = 0;
+        loop {
+            let byte: u8 = match chunk.get(index) {
+                Some(&byte) => byte,
+                None => return chunk.len(),
+            };
+            index += 1;
+            length += byte as usize;
+            if byte != 0xFF {
+                break;
+            }
+        }
+
+        if !(position == 0 && length == 0) {
+            let start: usize = buffer.len().saturating_sub(position);
+            if start < buffer.len() {
+                for offset in 0..length {
+                    let byte: u8 = buffer[start + offset];
+                    buffer.push(byte);
+                }
+            }
+        }
+        match chunk.get(index) {
+            Some(&byte) => {
+                buffer.push(byte);
+                index + 1
+            }
+            None => chunk.len(),
+        }
+    }
+
+    /// Décode un jeton selon l'ancien format de largeur fixe (3 octets : position sur 12
+    /// bits, longueur sur 4 bits). Conservé uniquement pour que [`LZ77::decompress_frame`]
+    /// puisse encore décoder les trames version 1, produites avant l'introduction de
+    /// l'encodage étendu.
+    fn decode_legacy(buffer: &mut Vec<u8>, chunk: &[u8]) {
+        let position: usize = chunk[0] as usize | ((chunk[1] as usize & 0x0F) << 8);
+        let length: usize = (chunk[1] >> 4) as usize;
+
+        if !(position == 0 && length == 0) {
+            let start: usize = buffer.len().saturating_sub(position);
+            if start < buffer.len() {
+                for offset in 0..length {
+                    let byte: u8 = buffer[start + offset];
+                    buffer.push(byte);
+                }
             }
         }
         buffer.push(chunk[2]);
@@ -210,41 +662,137 @@ impl LZ77 {
         max_dictionary_size: usize,
         lookahead_buffer_size: usize,
     ) -> Vec<u8> {
-        // la longueur de la donnée
-        let raw_data_length: usize = raw_data.len();
+        LZ77::compress_with_max_chain(
+            raw_data,
+            max_dictionary_size,
+            lookahead_buffer_size,
+            DEFAULT_MAX_CHAIN,
+        )
+    }
+
+    /// Compresse les données brutes en utilisant l'algorithme LZ77, avec un contrôle explicite
+    /// de la profondeur de parcours de la chaîne de hachage (voir [`LZ77::set_max_chain`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `raw_data` - Données brutes à compresser.
+    /// * `max_dictionary_size` - Taille maximale du dictionnaire de recherche.
+    /// * `lookahead_buffer_size` - Taille du tampon de recherche.
+    /// * `max_chain` - Nombre maximal de maillons parcourus par recherche de correspondance.
+    ///
+    /// # Returns
+    ///
+    /// Un vecteur contenant les données compressées.
+    fn compress_with_max_chain(
+        raw_data: &[u8],
+        max_dictionary_size: usize,
+        lookahead_buffer_size: usize,
+        max_chain: usize,
+    ) -> Vec<u8> {
+        LZ77::compress_with_dictionary(
+            raw_data,
+            &[],
+            max_dictionary_size,
+            lookahead_buffer_size,
+            max_chain,
+            false,
+        )
+    }
+
+    /// Compresse `raw_data` en amorçant le tampon de recherche avec `dictionary` : les
+    /// correspondances peuvent se référer aux octets du dictionnaire comme s'ils précédaient
+    /// `raw_data`, mais seul le flux de jetons couvrant `raw_data` est renvoyé. `dictionary`
+    /// peut être vide, auquel cas le comportement est identique à l'algorithme sans
+    /// dictionnaire.
+    ///
+    /// Si `lazy_matching` est activé, la meilleure correspondance trouvée en position `i`
+    /// n'est validée que si la position `i + 1` n'offre pas une correspondance strictement
+    /// plus longue ; dans ce dernier cas, un simple littéral est émis pour l'octet `i` et la
+    /// décision est reportée d'un octet. Cela augmente le taux de compression au prix d'une
+    /// recherche supplémentaire par position.
+    fn compress_with_dictionary(
+        raw_data: &[u8],
+        dictionary: &[u8],
+        max_dictionary_size: usize,
+        lookahead_buffer_size: usize,
+        max_chain: usize,
+        lazy_matching: bool,
+    ) -> Vec<u8> {
+        // la donnée amorcée par le dictionnaire, sur laquelle porte la recherche de
+        // correspondances ; seules les positions à partir de `dictionary_length` produisent
+        // des jetons.
+        let dictionary_length: usize = dictionary.len();
+        let mut combined: Vec<u8> = Vec::with_capacity(dictionary_length + raw_data.len());
+        combined.extend_from_slice(dictionary);
+        combined.extend_from_slice(raw_data);
+        let combined_length: usize = combined.len();
+
         // contient la donnée compresser
         let mut compressed_data: Vec<u8> = Vec::new();
-        // stocke le carractere dans l'encodage
-        let mut next_character: u8;
-        // la taille du dictionnaire de recherche
-        let search_buffer_length: usize = max_dictionary_size; 
-        // le curseur dans le dictionnaire de recherche
-        let mut search_buffer_index: usize;
-        // la taille du tampon de recherche
-        let ahead_buffer_length: usize = lookahead_buffer_size;
-        // le curseur dans le tampon de recherche
-        let mut ahead_buffer_index: usize;
-        let mut cursor: usize;
-        let mut length: usize;
-        let mut i: usize = 0;
-        while i < raw_data_length {
-            // le max entre et 0
-            search_buffer_index = std::cmp::max(i.saturating_sub(search_buffer_length), 0);
-            ahead_buffer_index = std::cmp::min(i + ahead_buffer_length, raw_data_length);
-
-            (cursor, length) = find_longest_match(
-                &raw_data[search_buffer_index..i],
-                &raw_data[i..ahead_buffer_index],
-            );
+        // la taille du dictionnaire de recherche, bornée à la distance maximale représentable
+        let search_buffer_length: usize = std::cmp::min(max_dictionary_size, MAX_DISTANCE);
+        // la taille du tampon de recherche, bornée à la longueur de correspondance maximale
+        let ahead_buffer_length: usize = std::cmp::min(lookahead_buffer_size, MAX_LENGTH);
+        // chaîne de hachage utilisée pour retrouver rapidement les correspondances passées
+        let mut chain: HashChain = HashChain::new(combined_length, max_chain);
+        // position jusqu'à laquelle la chaîne de hachage a déjà été alimentée
+        let mut inserted_up_to: usize = 0;
+        // file des dernières distances de correspondance utilisées, pour émettre des jetons de
+        // correspondance répétée compacts sur les données à structure répétitive
+        let mut recent_offsets: RecentOffsets = RecentOffsets::new();
 
-            if i + length >= raw_data_length {
-                next_character = 0;
-            } else {
-                next_character = raw_data[i + length];
+        let insert_up_to = |chain: &mut HashChain, inserted_up_to: &mut usize, target: usize| {
+            while *inserted_up_to < target {
+                chain.insert(*inserted_up_to, &combined);
+                *inserted_up_to += 1;
+            }
+        };
+
+        // amorce la chaîne avec le dictionnaire, pour que le premier octet de `raw_data`
+        // puisse déjà s'y référer
+        insert_up_to(&mut chain, &mut inserted_up_to, dictionary_length);
+
+        let mut i: usize = dictionary_length;
+        while i < combined_length {
+            insert_up_to(&mut chain, &mut inserted_up_to, i);
+
+            let ahead_len: usize = std::cmp::min(ahead_buffer_length, combined_length - i);
+            let (cursor, length) = chain.find_match(i, &combined, search_buffer_length, ahead_len);
+
+            if lazy_matching && length > 0 && i + 1 < combined_length {
+                // rend `i` visible à la recherche sur `i + 1`, sans quoi on ne verrait pas une
+                // correspondance qui commence exactement là où `i` se trouve.
+                insert_up_to(&mut chain, &mut inserted_up_to, i + 1);
+
+                let ahead_len_next: usize =
+                    std::cmp::min(ahead_buffer_length, combined_length - (i + 1));
+                let (_, length_next) =
+                    chain.find_match(i + 1, &combined, search_buffer_length, ahead_len_next);
+
+                if length_next > length {
+                    // diffère : émet un littéral pour `i` et retente la décision à `i + 1`.
+                    LZ77::encode(&mut compressed_data, &mut recent_offsets, 0, 0, Some(combined[i]));
+                    i += 1;
+                    continue;
+                }
             }
-            LZ77::encode(&mut compressed_data, cursor, length, next_character);
 
-            i += length + 1;
+            let next_character: Option<u8> = if i + length >= combined_length {
+                None
+            } else {
+                Some(combined[i + length])
+            };
+            LZ77::encode(
+                &mut compressed_data,
+                &mut recent_offsets,
+                cursor,
+                length,
+                next_character,
+            );
+
+            let next_i: usize = std::cmp::min(i + length + 1, combined_length);
+            insert_up_to(&mut chain, &mut inserted_up_to, next_i);
+            i = next_i;
         }
 
         compressed_data
@@ -284,15 +832,181 @@ impl LZ77 {
     pub fn decompress(compressed_data: &[u8]) -> Vec<u8> {
         let compressed_data_length: usize = compressed_data.len();
         let mut raw_data: Vec<u8> = Vec::new();
+        let mut recent_offsets: RecentOffsets = RecentOffsets::new();
 
-        for i in (0..compressed_data_length).step_by(3) {
-            LZ77::decode(&mut raw_data, &compressed_data[i..i + 3]);
-        }
-        if raw_data.len() > 0 && raw_data[raw_data.len() - 1] == 0 {
-            return raw_data[..raw_data.len() - 1].to_vec();
+        let mut i: usize = 0;
+        while i < compressed_data_length {
+            i += LZ77::decode(&mut raw_data, &mut recent_offsets, &compressed_data[i..]);
         }
         raw_data
     }
+
+    /// Crée un [`Encoder`] qui compresse en continu les octets qui lui sont écrits et les
+    /// transmet à `writer`, sans jamais matérialiser l'intégralité de l'entrée en mémoire.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use std::io::Write;
+    /// use lzrs::LZ77;
+    ///
+    /// // Crée une nouvelle instance de LZ77.
+    /// let lz77 = LZ77::new();
+    ///
+    /// // Crée un encodeur qui écrit dans un `Vec<u8>`.
+    /// let mut encoder = lz77.encoder(Vec::new());
+    /// encoder.write_all(b"hello world").unwrap();
+    /// let compressed_data = encoder.finish().unwrap();
+    /// ```
+    pub fn encoder<W: std::io::Write>(&self, writer: W) -> Encoder<W> {
+        Encoder::new(self, writer)
+    }
+
+    /// Crée un [`Decoder`] qui décompresse en continu les jetons lus depuis `reader`.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use std::io::Read;
+    /// use lzrs::{LZ77, LZ};
+    ///
+    /// // Crée une nouvelle instance de LZ77.
+    /// let lz77 = LZ77::new();
+    ///
+    /// // Compresse la phrase d'abord pour obtenir des données à décompresser.
+    /// let compressed_data = lz77.compress(b"hello world");
+    ///
+    /// // Crée un décodeur qui lit depuis un tampon compressé.
+    /// let mut decoder = lz77.decoder(&compressed_data[..]);
+    /// let mut decompressed_data = Vec::new();
+    /// decoder.read_to_end(&mut decompressed_data).unwrap();
+    /// assert_eq!(decompressed_data, b"hello world");
+    /// ```
+    pub fn decoder<R: std::io::Read>(&self, reader: R) -> Decoder<R> {
+        Decoder::new(self, reader)
+    }
+
+    /// Compresse `raw_data` et enveloppe le résultat dans une trame auto-descriptive : un
+    /// nombre magique, la configuration utilisée (`max_dictionary_size`,
+    /// `lookahead_buffer_size`), la taille d'origine, le flux de jetons, puis une somme de
+    /// contrôle CRC-32 des données brutes. Voir [`LZ77::decompress_frame`] pour l'opération
+    /// inverse.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use lzrs::LZ77;
+    ///
+    /// // Données brutes à compresser.
+    /// let input_data = b"hello world";
+    ///
+    /// // Compresse la donnée dans une trame auto-descriptive.
+    /// let framed_data = LZ77::compress_frame(input_data, 4095, 15);
+    ///
+    /// // Décompresse la trame : la configuration est retrouvée automatiquement.
+    /// let decompressed_data = LZ77::decompress_frame(&framed_data).unwrap();
+    /// assert_eq!(decompressed_data, input_data);
+    /// ```
+    pub fn compress_frame(
+        raw_data: &[u8],
+        max_dictionary_size: usize,
+        lookahead_buffer_size: usize,
+    ) -> Vec<u8> {
+        let token_stream: Vec<u8> =
+            LZ77::compress(raw_data, max_dictionary_size, lookahead_buffer_size);
+        encode_frame(
+            raw_data,
+            &token_stream,
+            max_dictionary_size,
+            lookahead_buffer_size,
+        )
+    }
+
+    /// Décompresse une trame produite par [`LZ77::compress_frame`].
+    ///
+    /// La configuration (`max_dictionary_size`, `lookahead_buffer_size`) est lue depuis
+    /// l'en-tête de la trame : l'appelant n'a pas besoin de la connaître à l'avance.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une [`FrameError`] si le nombre magique est invalide, si la version de la
+    /// trame n'est pas prise en charge, si la trame est tronquée, ou si la somme de contrôle
+    /// calculée sur les données décompressées ne correspond pas à celle stockée dans la
+    /// trame (données corrompues).
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use lzrs::LZ77;
+    ///
+    /// let input_data = b"hello world";
+    /// let framed_data = LZ77::compress_frame(input_data, 4095, 15);
+    ///
+    /// let decompressed_data = LZ77::decompress_frame(&framed_data).unwrap();
+    /// assert_eq!(decompressed_data, input_data);
+    /// ```
+    pub fn decompress_frame(framed: &[u8]) -> Result<Vec<u8>, FrameError> {
+        let decoded = decode_frame(framed)?;
+
+        let mut raw_data: Vec<u8> = Vec::new();
+        match decoded.version {
+            1 => {
+                // trame produite avant l'introduction de l'encodage étendu : jetons de 3 octets
+                for chunk in decoded.token_stream.chunks(3) {
+                    if chunk.len() < 3 {
+                        break;
+                    }
+                    LZ77::decode_legacy(&mut raw_data, chunk);
+                }
+            }
+            2 => {
+                // trame produite avant l'introduction de la file de distances récentes
+                let mut i: usize = 0;
+                while i < decoded.token_stream.len() {
+                    i += LZ77::decode_without_recent_offsets(&mut raw_data, &decoded.token_stream[i..]);
+                }
+            }
+            3 => {
+                // trame produite avant l'introduction du drapeau de présence du caractère
+                // suivant (voir [`LZ77::decode_v3`])
+                let mut recent_offsets: RecentOffsets = RecentOffsets::new();
+                let mut i: usize = 0;
+                while i < decoded.token_stream.len() {
+                    i += LZ77::decode_v3(&mut raw_data, &mut recent_offsets, &decoded.token_stream[i..]);
+                }
+            }
+            4 => {
+                // trame produite avant l'introduction de l'étiquette de position compacte
+                // (voir [`LZ77::decode_v4`])
+                let mut recent_offsets: RecentOffsets = RecentOffsets::new();
+                let mut i: usize = 0;
+                while i < decoded.token_stream.len() {
+                    i += LZ77::decode_v4(&mut raw_data, &mut recent_offsets, &decoded.token_stream[i..]);
+                }
+            }
+            _ => {
+                let mut recent_offsets: RecentOffsets = RecentOffsets::new();
+                let mut i: usize = 0;
+                while i < decoded.token_stream.len() {
+                    i += LZ77::decode(&mut raw_data, &mut recent_offsets, &decoded.token_stream[i..]);
+                }
+            }
+        }
+        raw_data.truncate(decoded.original_length);
+
+        if crc32(&raw_data) != decoded.checksum {
+            return Err(FrameError::ChecksumMismatch);
+        }
+
+        Ok(raw_data)
+    }
+}
+
+impl Default for LZ77 {
+    /// Équivalent à [`LZ77::new`].
+    fn default() -> Self {
+        LZ77::new()
+    }
 }
 
 impl Clone for LZ77 {
@@ -300,6 +1014,9 @@ impl Clone for LZ77 {
         LZ77 {
             max_dictionary_size: self.get_max_dictionary_size(),
             lookahead_buffer_size: self.get_lookahead_buffer_size(),
+            max_chain: self.get_max_chain(),
+            preset_dictionary: self.get_dictionary().to_vec(),
+            lazy_matching: self.get_lazy_matching(),
         }
     }
 }
@@ -334,10 +1051,13 @@ impl LZ for LZ77 {
     /// assert_eq!(decompressed_data, input_data);
     /// ```
     fn compress(&self, raw_data: &[u8]) -> Vec<u8> {
-        LZ77::compress(
+        LZ77::compress_with_dictionary(
             raw_data,
+            self.get_dictionary(),
             self.get_max_dictionary_size(),
             self.get_lookahead_buffer_size(),
+            self.get_max_chain(),
+            self.get_lazy_matching(),
         )
     }
 
@@ -370,7 +1090,21 @@ impl LZ for LZ77 {
     /// assert_eq!(decompressed_data, input_data);
     /// ```
     fn decompress(&self, compressed_data: &[u8]) -> Vec<u8> {
-        LZ77::decompress(compressed_data)
+        if self.preset_dictionary.is_empty() {
+            return LZ77::decompress(compressed_data);
+        }
+
+        // amorce le buffer de décompression avec le dictionnaire, pour que les jetons
+        // puissent s'y référer, puis retire ces octets du résultat final.
+        let mut buffer: Vec<u8> = self.preset_dictionary.clone();
+        let compressed_data_length: usize = compressed_data.len();
+        let mut recent_offsets: RecentOffsets = RecentOffsets::new();
+        let mut i: usize = 0;
+        while i < compressed_data_length {
+            i += LZ77::decode(&mut buffer, &mut recent_offsets, &compressed_data[i..]);
+        }
+
+        buffer.split_off(self.preset_dictionary.len())
     }
 }
 
@@ -395,4 +1129,129 @@ mod tests {
         // Vérifie que les données décompressées correspondent à la phrase d'origine.
         assert_eq!(decompressed_data, phrase);
     }
+
+    #[test]
+    fn compression_and_decompression_with_dictionary() {
+        // Crée une instance de LZ77 amorcée avec un dictionnaire.
+        let lz77 : LZ77 = LZ77::with_dictionary(b"Une phrase d'exemple");
+
+        // Phrase courte qui partage un préfixe avec le dictionnaire.
+        let phrase : &[u8; 21] = b"Une phrase differente";
+
+        // Compresse la phrase.
+        let compressed_data : Vec<u8> = lz77.compress(phrase);
+
+        // Décompresse les données compressées avec la même instance (même dictionnaire).
+        let decompressed_data : Vec<u8> = lz77.decompress(&compressed_data);
+
+        // Vérifie que les données décompressées correspondent à la phrase d'origine, sans le
+        // dictionnaire.
+        assert_eq!(decompressed_data, phrase);
+    }
+
+    #[test]
+    fn compression_and_decompression_with_lazy_matching() {
+        // Crée une instance de LZ77 avec l'appariement paresseux activé.
+        let mut lz77 : LZ77 = LZ77::new();
+        lz77.set_lazy_matching(true);
+
+        // Phrase à compresser.
+        let phrase : &[u8; 41] = b"Une phrase d'exemple Une phrase d'exemple";
+
+        // Compresse la phrase.
+        let compressed_data : Vec<u8> = lz77.compress(phrase);
+
+        // Décompresse les données compressées.
+        let decompressed_data : Vec<u8> = lz77.decompress(&compressed_data);
+
+        // Vérifie que les données décompressées correspondent à la phrase d'origine.
+        assert_eq!(decompressed_data, phrase);
+    }
+
+    #[test]
+    fn compression_and_decompression_with_long_run() {
+        // Crée une nouvelle instance de LZ77.
+        let lz77 : LZ77 = LZ77::new();
+
+        // Donnée très répétitive, qui aurait débordé l'ancien encodage de longueur sur 4 bits.
+        let phrase : Vec<u8> = vec![b'a'; 1000];
+
+        // Compresse la donnée.
+        let compressed_data : Vec<u8> = lz77.compress(&phrase);
+
+        // Avec le tampon de recherche par défaut (voir `LZ77::new`), une correspondance doit
+        // pouvoir dépasser les 15 octets du format d'origine et exercer la continuation du
+        // varint de longueur (un octet `0xFF`, voir `LZ77::encode`) : sans quoi la sortie
+        // resterait de taille proportionnelle à l'entrée au lieu de tenir sur une poignée de
+        // jetons.
+        assert!(
+            compressed_data.contains(&0xFF),
+            "une correspondance de plus de 254 octets devrait émettre une continuation de longueur"
+        );
+        assert!(
+            compressed_data.len() < 30,
+            "1000 octets identiques devraient se compresser en une poignée de jetons, pas {} octets",
+            compressed_data.len()
+        );
+
+        // Décompresse les données compressées.
+        let decompressed_data : Vec<u8> = lz77.decompress(&compressed_data);
+
+        // Vérifie que les données décompressées correspondent à la donnée d'origine.
+        assert_eq!(decompressed_data, phrase);
+    }
+
+    #[test]
+    fn compression_and_decompression_with_long_run_via_frame() {
+        // Donnée très répétitive, compressée et décompressée via le format de trame.
+        let phrase : Vec<u8> = vec![b'z'; 1000];
+
+        // Compresse la donnée dans une trame auto-descriptive.
+        let framed_data : Vec<u8> = LZ77::compress_frame(&phrase, 4095, 15);
+
+        // Décompresse la trame.
+        let decompressed_data : Vec<u8> = LZ77::decompress_frame(&framed_data).unwrap();
+
+        // Vérifie que les données décompressées correspondent à la donnée d'origine.
+        assert_eq!(decompressed_data, phrase);
+    }
+
+    #[test]
+    fn compression_and_decompression_with_repeated_offset() {
+        // Crée une nouvelle instance de LZ77. Le tampon de recherche est borné à 15 octets pour
+        // qu'une seule correspondance ne couvre pas toute la donnée : chaque correspondance
+        // suivante réutilise alors la même distance de 4 octets.
+        let mut lz77 : LZ77 = LZ77::new();
+        lz77.set_lookahead_buffer_size(15);
+
+        // Donnée à structure fixe (enregistrements de 4 octets) qui référence toujours la même
+        // distance arrière d'un enregistrement à l'autre : le cas visé par la file de distances
+        // récentes.
+        let mut phrase : Vec<u8> = Vec::new();
+        for _ in 0..50 {
+            phrase.extend_from_slice(b"abcd");
+        }
+
+        // Compresse la donnée.
+        let compressed_data : Vec<u8> = lz77.compress(&phrase);
+
+        // Le jeton de correspondance répétée ne coûte qu'un octet de position (voir
+        // `POSITION_TAG_FULL_DISTANCE`), contre 3 pour une distance complète : 4 littéraux de
+        // 4 octets, une correspondance à distance complète de 6 octets, puis 11 correspondances
+        // répétées de 4 octets et une dernière de 3 octets (sans caractère suivant), soit 69
+        // octets, contre 93 si chaque correspondance portait sa distance complète.
+        assert_eq!(
+            compressed_data.len(),
+            69,
+            "la file de distances récentes devrait réduire la sortie : {} octets pour {} octets d'entrée",
+            compressed_data.len(),
+            phrase.len()
+        );
+
+        // Décompresse les données compressées.
+        let decompressed_data : Vec<u8> = lz77.decompress(&compressed_data);
+
+        // Vérifie que les données décompressées correspondent à la donnée d'origine.
+        assert_eq!(decompressed_data, phrase);
+    }
 }
@@ -0,0 +1,79 @@
+//! File LRU des distances de correspondance récemment utilisées par [`crate::LZ77`].
+//!
+//! Les formats à structure fixe (tables, lignes de pixels, enregistrements) référencent
+//! souvent la même distance arrière d'une correspondance à l'autre. [`RecentOffsets`] retient
+//! les [`CAPACITY`] dernières distances utilisées pour que [`crate::LZ77::encode`] puisse
+//! émettre un jeton "correspondance répétée" compact (un emplacement de la file) au lieu de
+//! ré-encoder la distance complète, à la manière de la file de distances récentes des
+//! compresseurs de la famille LZMA.
+
+/// Nombre de distances récentes conservées.
+pub(crate) const CAPACITY: usize = 3;
+
+/// File LRU des dernières distances de correspondance utilisées.
+///
+/// `offsets[0]` est la distance la plus récemment utilisée. Une nouvelle instance doit être
+/// créée pour chaque flux compressé indépendant : [`LZ77::compress`] et [`LZ77::decompress`]
+/// (ainsi que [`Encoder`] et [`Decoder`]) en maintiennent chacun une, qui doit rester
+/// synchronisée entre l'encodage et le décodage pour que les jetons de correspondance répétée
+/// se résolvent vers la bonne distance.
+///
+/// [`LZ77::compress`]: crate::LZ77::compress
+/// [`LZ77::decompress`]: crate::LZ77::decompress
+/// [`Encoder`]: crate::Encoder
+/// [`Decoder`]: crate::Decoder
+#[derive(Debug, Default)]
+pub struct RecentOffsets {
+    offsets: Vec<usize>,
+}
+
+impl RecentOffsets {
+    /// Crée une file de distances récentes vide.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use lzrs::RecentOffsets;
+    ///
+    /// let recent_offsets = RecentOffsets::new();
+    /// ```
+    pub fn new() -> Self {
+        RecentOffsets {
+            offsets: Vec::with_capacity(CAPACITY),
+        }
+    }
+
+    /// Enregistre l'utilisation de `distance` par une correspondance réelle.
+    ///
+    /// Si `distance` figure déjà dans la file, la fait passer en tête et renvoie son ancien
+    /// emplacement. Sinon, l'insère en tête (en évinçant la plus ancienne entrée si la file est
+    /// déjà pleine) et renvoie `None`.
+    pub(crate) fn record(&mut self, distance: usize) -> Option<u8> {
+        if let Some(slot) = self.offsets.iter().position(|&offset| offset == distance) {
+            self.offsets.remove(slot);
+            self.offsets.insert(0, distance);
+            Some(slot as u8)
+        } else {
+            self.offsets.insert(0, distance);
+            self.offsets.truncate(CAPACITY);
+            None
+        }
+    }
+
+    /// Résout l'emplacement `slot` d'un jeton de correspondance répétée vers sa distance
+    /// réelle, en la faisant passer en tête de la file.
+    ///
+    /// Un flux de jetons produit par [`crate::LZ77::encode`] ne référence jamais un `slot` qui
+    /// n'a pas encore été rempli, mais un flux corrompu ou forgé le pourrait : dans ce cas,
+    /// renvoie `0` sans modifier la file plutôt que de paniquer, laissant la corruption se
+    /// propager jusqu'à la vérification de la somme de contrôle (voir
+    /// [`crate::LZ77::decompress_frame`]).
+    pub(crate) fn resolve(&mut self, slot: u8) -> usize {
+        if slot as usize >= self.offsets.len() {
+            return 0;
+        }
+        let distance: usize = self.offsets.remove(slot as usize);
+        self.offsets.insert(0, distance);
+        distance
+    }
+}
@@ -0,0 +1,341 @@
+//! Compression et décompression en continu (streaming) par dessus `std::io::{Read, Write}`.
+//!
+//! [`crate::LZ77::compress`] et [`crate::LZ77::decompress`] exigent que toute la donnée tienne
+//! en mémoire dans un seul `&[u8]`/`Vec<u8>`. [`Encoder`] et [`Decoder`] permettent de traiter
+//! un flux au fur et à mesure qu'il arrive (fichier, socket, ...), sans jamais matérialiser
+//! plus que le dictionnaire de recherche et le tampon d'anticipation en mémoire.
+
+use std::io::{self, Read, Write};
+
+use crate::hash_chain::HashChain;
+use crate::lz77::{MAX_DISTANCE, MAX_LENGTH, POSITION_TAG_FULL_DISTANCE, POSITION_TAG_NO_MATCH};
+use crate::{RecentOffsets, LZ77};
+
+/// Compresseur qui écrit des jetons LZ77 au fur et à mesure que des octets lui sont écrits.
+///
+/// `buffer` contient les octets pas encore glissés hors de la fenêtre de recherche : les
+/// `scanned` premiers ont déjà été traduits en jetons (c'est le dictionnaire de recherche), le
+/// reste est le tampon d'anticipation pas encore consommé. Les correspondances sont retrouvées
+/// via une [`HashChain`], comme pour [`LZ77::compress`] ; comme la longueur totale du flux
+/// n'est pas connue à l'avance, `buffer` et la chaîne de hachage sont périodiquement décalés
+/// (voir [`HashChain::rebase`]) dès que `scanned` dépasse `max_dictionary_size`, pour ne jamais
+/// matérialiser plus que le dictionnaire de recherche et le tampon d'anticipation en mémoire.
+pub struct Encoder<W: Write> {
+    writer: W,
+    max_dictionary_size: usize,
+    lookahead_buffer_size: usize,
+    search_buffer_length: usize,
+    buffer: Vec<u8>,
+    /// Nombre d'octets de `buffer` déjà traduits en jetons.
+    scanned: usize,
+    /// Nombre d'octets de `buffer` déjà insérés dans `chain`.
+    inserted_up_to: usize,
+    chain: HashChain,
+    recent_offsets: RecentOffsets,
+}
+
+impl<W: Write> Encoder<W> {
+    /// Crée un nouvel `Encoder` écrivant les jetons compressés dans `writer`, en utilisant la
+    /// configuration (taille de dictionnaire, taille du tampon d'anticipation) de `lz77`.
+    pub(crate) fn new(lz77: &LZ77, writer: W) -> Self {
+        let max_dictionary_size: usize = lz77.get_max_dictionary_size();
+        Encoder {
+            writer,
+            max_dictionary_size,
+            lookahead_buffer_size: lz77.get_lookahead_buffer_size(),
+            search_buffer_length: std::cmp::min(max_dictionary_size, MAX_DISTANCE),
+            buffer: Vec::new(),
+            scanned: 0,
+            inserted_up_to: 0,
+            chain: HashChain::new(0, lz77.get_max_chain()),
+            recent_offsets: RecentOffsets::new(),
+        }
+    }
+
+    /// Insère dans `chain` toutes les positions de `buffer` jusqu'à `target` (exclu) qui n'y
+    /// sont pas encore.
+    fn insert_up_to(&mut self, target: usize) {
+        while self.inserted_up_to < target {
+            self.chain.insert(self.inserted_up_to, &self.buffer);
+            self.inserted_up_to += 1;
+        }
+    }
+
+    /// Abandonne le début de `buffer` dès que la portion déjà traduite en jetons (`scanned`)
+    /// dépasse `max_dictionary_size`, et décale `chain` d'autant pour que ses positions restent
+    /// valides (voir [`HashChain::rebase`]).
+    fn slide(&mut self) {
+        if self.scanned > self.max_dictionary_size {
+            let excess: usize = self.scanned - self.max_dictionary_size;
+            self.buffer.drain(..excess);
+            self.scanned -= excess;
+            self.inserted_up_to -= excess;
+            self.chain.rebase(excess);
+        }
+    }
+
+    /// Émet autant de jetons que possible à partir des octets en attente.
+    ///
+    /// Tant que `is_final` vaut `false`, s'arrête dès qu'il ne reste plus assez d'octets en
+    /// attente pour garantir qu'une correspondance plus longue ne sera pas découverte par la
+    /// suite. Quand `is_final` vaut `true` (voir [`Encoder::finish`]), vide entièrement
+    /// les octets en attente de `buffer`, y compris le dernier caractère littéral.
+    fn drain_tokens(&mut self, is_final: bool) -> io::Result<()> {
+        loop {
+            let available: usize = self.buffer.len() - self.scanned;
+            if available == 0 {
+                break;
+            }
+            if !is_final && available <= self.lookahead_buffer_size {
+                break;
+            }
+
+            self.insert_up_to(self.scanned);
+
+            let ahead_len: usize =
+                std::cmp::min(std::cmp::min(self.lookahead_buffer_size, MAX_LENGTH), available);
+            let (cursor, length) =
+                self.chain
+                    .find_match(self.scanned, &self.buffer, self.search_buffer_length, ahead_len);
+
+            let next_character: Option<u8> = if length < available {
+                Some(self.buffer[self.scanned + length])
+            } else {
+                None
+            };
+            let mut token: Vec<u8> = Vec::with_capacity(4);
+            LZ77::encode(&mut token, &mut self.recent_offsets, cursor, length, next_character);
+            self.writer.write_all(&token)?;
+
+            let next_scanned: usize = std::cmp::min(self.scanned + length + 1, self.buffer.len());
+            self.insert_up_to(next_scanned);
+            self.scanned = next_scanned;
+            self.slide();
+        }
+        Ok(())
+    }
+
+    /// Termine la compression : vide le tampon d'attente (y compris le dernier littéral), puis
+    /// renvoie le `writer` sous-jacent.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.drain_tokens(true)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        self.drain_tokens(false)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Décompresseur qui lit des jetons LZ77 de longueur variable depuis un `Read` et restitue les
+/// octets décompressés au fur et à mesure.
+///
+/// `window` contient les octets décompressés les plus récents, utilisés comme dictionnaire
+/// pour résoudre les prochaines références arrière ; il est tronqué à `max_dictionary_size`
+/// octets une fois qu'ils ont été renvoyés à l'appelant.
+pub struct Decoder<R: Read> {
+    reader: R,
+    max_dictionary_size: usize,
+    window: Vec<u8>,
+    returned: usize,
+    recent_offsets: RecentOffsets,
+}
+
+impl<R: Read> Decoder<R> {
+    /// Crée un nouveau `Decoder` lisant des jetons compressés depuis `reader`, en utilisant la
+    /// configuration de `lz77`.
+    pub(crate) fn new(lz77: &LZ77, reader: R) -> Self {
+        Decoder {
+            reader,
+            max_dictionary_size: lz77.get_max_dictionary_size(),
+            window: Vec::new(),
+            returned: 0,
+            recent_offsets: RecentOffsets::new(),
+        }
+    }
+
+    /// Abandonne le début de `window` dès qu'il a déjà été renvoyé à l'appelant et qu'il
+    /// dépasse la taille maximale du dictionnaire.
+    fn trim(&mut self) {
+        if self.returned > self.max_dictionary_size {
+            let drop: usize = self.returned - self.max_dictionary_size;
+            self.window.drain(..drop);
+            self.returned -= drop;
+        }
+    }
+
+    /// Lit un jeton de longueur variable depuis `reader` et l'ajoute à `window`, en résolvant
+    /// au besoin une correspondance répétée via `recent_offsets` (voir [`LZ77::decode`]).
+    /// Retourne `Ok(false)` si le flux s'est terminé proprement avant le début d'un nouveau
+    /// jeton.
+    fn read_token(&mut self) -> io::Result<bool> {
+        let mut tag: [u8; 1] = [0; 1];
+        match self.reader.read_exact(&mut tag) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(false),
+            Err(e) => return Err(e),
+        }
+
+        let position: usize = if tag[0] == POSITION_TAG_NO_MATCH {
+            0
+        } else if tag[0] == POSITION_TAG_FULL_DISTANCE {
+            let mut distance_bytes: [u8; 2] = [0; 2];
+            self.reader.read_exact(&mut distance_bytes)?;
+            let distance: usize = u16::from_le_bytes(distance_bytes) as usize;
+            self.recent_offsets.record(distance);
+            distance
+        } else {
+            self.recent_offsets.resolve(tag[0] - 1)
+        };
+
+        let mut length: usize = 0;
+        loop {
+            let mut byte: [u8; 1] = [0; 1];
+            self.reader.read_exact(&mut byte)?;
+            length += byte[0] as usize;
+            if byte[0] != 0xFF {
+                break;
+            }
+        }
+
+        let mut has_next_character: [u8; 1] = [0; 1];
+        self.reader.read_exact(&mut has_next_character)?;
+        let next_character: Option<u8> = if has_next_character[0] != 0 {
+            let mut byte: [u8; 1] = [0; 1];
+            self.reader.read_exact(&mut byte)?;
+            Some(byte[0])
+        } else {
+            None
+        };
+
+        if !(position == 0 && length == 0) {
+            // Recopie octet par octet : une correspondance peut se chevaucher avec les octets
+            // qu'elle vient elle-même de produire (distance inférieure à la longueur).
+            let start: usize = self.window.len().saturating_sub(position);
+            if start < self.window.len() {
+                for offset in 0..length {
+                    let byte: u8 = self.window[start + offset];
+                    self.window.push(byte);
+                }
+            }
+        }
+        if let Some(byte) = next_character {
+            self.window.push(byte);
+        }
+
+        Ok(true)
+    }
+}
+
+impl<R: Read> Read for Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.returned >= self.window.len() {
+            if !self.read_token()? {
+                return Ok(0);
+            }
+        }
+
+        let available: usize = self.window.len() - self.returned;
+        let to_copy: usize = std::cmp::min(available, buf.len());
+        buf[..to_copy].copy_from_slice(&self.window[self.returned..self.returned + to_copy]);
+        self.returned += to_copy;
+        self.trim();
+
+        Ok(to_copy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_with_small_input() {
+        // Crée une nouvelle instance de LZ77.
+        let lz77 : LZ77 = LZ77::new();
+
+        // Phrase courte, bien en-deçà de la taille du dictionnaire.
+        let phrase : &[u8] = b"hello world";
+
+        // Compresse en continu vers un `Vec<u8>`.
+        let mut encoder = lz77.encoder(Vec::new());
+        encoder.write_all(phrase).unwrap();
+        let compressed_data : Vec<u8> = encoder.finish().unwrap();
+
+        // Décompresse en continu depuis le `Vec<u8>` compressé.
+        let mut decoder = lz77.decoder(&compressed_data[..]);
+        let mut decompressed_data : Vec<u8> = Vec::new();
+        decoder.read_to_end(&mut decompressed_data).unwrap();
+
+        assert_eq!(decompressed_data, phrase);
+    }
+
+    #[test]
+    fn round_trip_with_input_larger_than_window() {
+        // Dictionnaire volontairement petit pour forcer plusieurs glissements de fenêtre
+        // (voir `Encoder::slide` et `Decoder::trim`) pendant la compression/décompression.
+        let mut lz77 : LZ77 = LZ77::new();
+        lz77.set_max_dictionary_size(64);
+        lz77.set_lookahead_buffer_size(8);
+
+        // Donnée bien plus grande que la fenêtre de recherche.
+        let phrase : Vec<u8> = (0..5000).map(|i| (i % 251) as u8).collect();
+
+        let mut encoder = lz77.encoder(Vec::new());
+        encoder.write_all(&phrase).unwrap();
+        let compressed_data : Vec<u8> = encoder.finish().unwrap();
+
+        let mut decoder = lz77.decoder(&compressed_data[..]);
+        let mut decompressed_data : Vec<u8> = Vec::new();
+        decoder.read_to_end(&mut decompressed_data).unwrap();
+
+        assert_eq!(decompressed_data, phrase);
+    }
+
+    #[test]
+    fn round_trip_with_highly_repetitive_input() {
+        // Crée une nouvelle instance de LZ77.
+        let lz77 : LZ77 = LZ77::new();
+
+        // Donnée très répétitive, qui exerce le jeton de correspondance répétée (voir
+        // `RecentOffsets`) et les correspondances de longueur étendue.
+        let phrase : Vec<u8> = vec![b'a'; 20000];
+
+        let mut encoder = lz77.encoder(Vec::new());
+        encoder.write_all(&phrase).unwrap();
+        let compressed_data : Vec<u8> = encoder.finish().unwrap();
+
+        let mut decoder = lz77.decoder(&compressed_data[..]);
+        let mut decompressed_data : Vec<u8> = Vec::new();
+        decoder.read_to_end(&mut decompressed_data).unwrap();
+
+        assert_eq!(decompressed_data, phrase);
+    }
+
+    #[test]
+    fn round_trip_with_trailing_null_byte() {
+        // Verrouille la correction du sentinelle en bande : le dernier octet du flux est un
+        // véritable `0x00` de donnée, qui ne doit pas être perdu au décodage.
+        let lz77 : LZ77 = LZ77::new();
+        let phrase : &[u8] = b"hello world hello world hello\x00";
+
+        let mut encoder = lz77.encoder(Vec::new());
+        encoder.write_all(phrase).unwrap();
+        let compressed_data : Vec<u8> = encoder.finish().unwrap();
+
+        let mut decoder = lz77.decoder(&compressed_data[..]);
+        let mut decompressed_data : Vec<u8> = Vec::new();
+        decoder.read_to_end(&mut decompressed_data).unwrap();
+
+        assert_eq!(decompressed_data, phrase);
+    }
+}